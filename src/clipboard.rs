@@ -0,0 +1,223 @@
+//! Clipboard access that doesn't clobber the user's clipboard permanently.
+//!
+//! The old hotkey handler (read) and `process_text` (write + simulated
+//! paste) went straight through `arboard::Clipboard`, so after a run
+//! whatever the user had copied before was gone for good. `ClipboardProvider`
+//! dispatches to whatever backend is actually available on the running
+//! platform -- preferring the X11/Wayland *primary* selection so the user
+//! doesn't even need to Ctrl+C first. Since the response is now typed
+//! directly at the cursor instead of pasted (see `gemini::call_gemini_api_streaming`),
+//! nothing here overwrites the clipboard as a side effect of processing a
+//! selection any more, so there's no snapshot/restore to do around that
+//! path: `set_clipboard` is only reached by the inspector's "copy to
+//! clipboard" button, a deliberate user action the user expects to
+//! overwrite the clipboard, the same as pressing Ctrl+C would.
+//!
+//! `detect_provider` shells out to `which` to find the active backend, so
+//! callers should run it once via `shared_provider` and reuse the result
+//! rather than re-detecting on every hotkey press or inspector click.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+/// A `ClipboardProvider` shared between the hotkey thread, the tray menu
+/// thread, and the inspector window, all of which need to read or write the
+/// clipboard but should agree on (and only detect) a single backend.
+pub type SharedClipboardProvider = Arc<Mutex<Box<dyn ClipboardProvider>>>;
+
+/// Detects the backend once and wraps it for sharing across threads.
+pub fn shared_provider() -> SharedClipboardProvider {
+    Arc::new(Mutex::new(detect_provider()))
+}
+
+/// A clipboard backend capable of reading/writing the system clipboard and,
+/// on platforms that have one, the primary selection.
+pub trait ClipboardProvider {
+    /// Name logged at startup so users can tell which backend is active.
+    fn name(&self) -> &'static str;
+
+    fn get_clipboard(&mut self) -> Result<String>;
+    fn set_clipboard(&mut self, text: &str) -> Result<()>;
+
+    /// Returns `Ok(String::new())` on platforms without a primary selection
+    /// rather than erroring, so callers can treat "empty" uniformly.
+    fn get_primary_selection(&mut self) -> Result<String>;
+}
+
+/// Picks the best available backend for the running platform, preferring
+/// native Wayland/X11 tools over the cross-platform `arboard` fallback
+/// since only they expose the primary selection.
+pub fn detect_provider() -> Box<dyn ClipboardProvider> {
+    let provider: Box<dyn ClipboardProvider> = if cfg!(target_os = "linux")
+        && command_exists("wl-copy")
+        && command_exists("wl-paste")
+    {
+        Box::new(WlClipboardProvider)
+    } else if cfg!(target_os = "linux") && command_exists("xclip") {
+        Box::new(XclipProvider)
+    } else if cfg!(target_os = "linux") && command_exists("xsel") {
+        Box::new(XselProvider)
+    } else if cfg!(target_os = "macos") && command_exists("pbcopy") && command_exists("pbpaste") {
+        Box::new(PbCopyProvider)
+    } else {
+        Box::new(ArboardProvider::default())
+    };
+
+    println!("📋 Clipboard backend: {}", provider.name());
+    provider
+}
+
+fn command_exists(name: &str) -> bool {
+    Command::new("which")
+        .arg(name)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+fn run_piped(cmd: &str, args: &[&str], input: &str) -> Result<()> {
+    let mut child = Command::new(cmd)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to spawn `{cmd}`"))?;
+    child
+        .stdin
+        .take()
+        .context("no stdin handle")?
+        .write_all(input.as_bytes())?;
+    child.wait().with_context(|| format!("`{cmd}` failed"))?;
+    Ok(())
+}
+
+fn run_captured(cmd: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(cmd)
+        .args(args)
+        .output()
+        .with_context(|| format!("failed to run `{cmd}`"))?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Wayland backend. `wl-paste -p` reads the primary selection.
+struct WlClipboardProvider;
+
+impl ClipboardProvider for WlClipboardProvider {
+    fn name(&self) -> &'static str {
+        "wl-clipboard"
+    }
+
+    fn get_clipboard(&mut self) -> Result<String> {
+        run_captured("wl-paste", &["--no-newline"])
+    }
+
+    fn set_clipboard(&mut self, text: &str) -> Result<()> {
+        run_piped("wl-copy", &[], text)
+    }
+
+    fn get_primary_selection(&mut self) -> Result<String> {
+        run_captured("wl-paste", &["--no-newline", "--primary"])
+    }
+}
+
+/// X11 backend via `xclip`, selected over `xsel` when both are present.
+struct XclipProvider;
+
+impl ClipboardProvider for XclipProvider {
+    fn name(&self) -> &'static str {
+        "xclip"
+    }
+
+    fn get_clipboard(&mut self) -> Result<String> {
+        run_captured("xclip", &["-selection", "clipboard", "-o"])
+    }
+
+    fn set_clipboard(&mut self, text: &str) -> Result<()> {
+        run_piped("xclip", &["-selection", "clipboard"], text)
+    }
+
+    fn get_primary_selection(&mut self) -> Result<String> {
+        run_captured("xclip", &["-selection", "primary", "-o"])
+    }
+}
+
+/// X11 backend via `xsel`, used when `xclip` isn't installed.
+struct XselProvider;
+
+impl ClipboardProvider for XselProvider {
+    fn name(&self) -> &'static str {
+        "xsel"
+    }
+
+    fn get_clipboard(&mut self) -> Result<String> {
+        run_captured("xsel", &["--clipboard", "--output"])
+    }
+
+    fn set_clipboard(&mut self, text: &str) -> Result<()> {
+        run_piped("xsel", &["--clipboard", "--input"], text)
+    }
+
+    fn get_primary_selection(&mut self) -> Result<String> {
+        run_captured("xsel", &["--primary", "--output"])
+    }
+}
+
+/// macOS backend. `pbpaste`/`pbcopy` have no primary-selection concept.
+struct PbCopyProvider;
+
+impl ClipboardProvider for PbCopyProvider {
+    fn name(&self) -> &'static str {
+        "pbcopy/pbpaste"
+    }
+
+    fn get_clipboard(&mut self) -> Result<String> {
+        run_captured("pbpaste", &[])
+    }
+
+    fn set_clipboard(&mut self, text: &str) -> Result<()> {
+        run_piped("pbcopy", &[], text)
+    }
+
+    fn get_primary_selection(&mut self) -> Result<String> {
+        Ok(String::new())
+    }
+}
+
+/// Fallback used on Windows (via the Win32 clipboard) and wherever no
+/// native selection tool was found. No primary-selection support.
+#[derive(Default)]
+struct ArboardProvider {
+    inner: Option<arboard::Clipboard>,
+}
+
+impl ArboardProvider {
+    fn clipboard(&mut self) -> Result<&mut arboard::Clipboard> {
+        if self.inner.is_none() {
+            self.inner = Some(arboard::Clipboard::new().context("failed to open clipboard")?);
+        }
+        Ok(self.inner.as_mut().unwrap())
+    }
+}
+
+impl ClipboardProvider for ArboardProvider {
+    fn name(&self) -> &'static str {
+        "arboard"
+    }
+
+    fn get_clipboard(&mut self) -> Result<String> {
+        self.clipboard()?.get_text().context("failed to read clipboard")
+    }
+
+    fn set_clipboard(&mut self, text: &str) -> Result<()> {
+        self.clipboard()?
+            .set_text(text.to_string())
+            .context("failed to write clipboard")
+    }
+
+    fn get_primary_selection(&mut self) -> Result<String> {
+        Ok(String::new())
+    }
+}
@@ -0,0 +1,290 @@
+//! Config and prompt loading, plus the resolved list of hotkey-bound
+//! actions built from them.
+//!
+//! `config.yaml` used to describe exactly one trigger chord bound to
+//! `prompt.txt`. It now describes a list of named `actions`, each with its
+//! own prompt and its own chord, so a user can bind "translate", "fix
+//! grammar" and "summarize" to different hotkeys without restarting.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize, Clone)]
+pub(crate) struct Config {
+    pub(crate) gemini_api_key: String,
+    #[serde(default)]
+    pub(crate) actions: Vec<ActionConfig>,
+    #[serde(default = "default_exit_use_ctrl")]
+    pub(crate) exit_use_ctrl: bool,
+    #[serde(default = "default_exit_use_shift")]
+    pub(crate) exit_use_shift: bool,
+    #[serde(default = "default_exit_use_alt")]
+    pub(crate) exit_use_alt: bool,
+    #[serde(default = "default_exit_key")]
+    pub(crate) exit_key: String,
+    #[serde(default)]
+    pub(crate) enable_inspector: bool,
+}
+
+/// One `actions` entry from `config.yaml`. Either `prompt` (inline text) or
+/// `prompt_file` (a path) must be set; if neither is, the action falls back
+/// to `prompt.txt` in the working directory. Unlike the synthesized
+/// "default" action, the executable's directory is not searched.
+#[derive(Debug, Deserialize, Clone)]
+pub(crate) struct ActionConfig {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) prompt: Option<String>,
+    #[serde(default)]
+    pub(crate) prompt_file: Option<String>,
+    #[serde(default = "default_use_ctrl")]
+    pub(crate) use_ctrl: bool,
+    #[serde(default = "default_use_shift")]
+    pub(crate) use_shift: bool,
+    #[serde(default = "default_use_alt")]
+    pub(crate) use_alt: bool,
+    #[serde(default = "default_trigger_key")]
+    pub(crate) key: String,
+}
+
+/// An action with its prompt already resolved to text, ready to be
+/// registered as a hotkey and matched on by name when it fires.
+#[derive(Debug, Clone)]
+pub(crate) struct Action {
+    pub(crate) name: String,
+    pub(crate) prompt: String,
+    pub(crate) use_ctrl: bool,
+    pub(crate) use_shift: bool,
+    pub(crate) use_alt: bool,
+    pub(crate) key: String,
+}
+
+fn default_use_ctrl() -> bool {
+    true
+}
+
+fn default_use_shift() -> bool {
+    true
+}
+
+fn default_use_alt() -> bool {
+    false
+}
+
+fn default_trigger_key() -> String {
+    "P".to_string()
+}
+
+fn default_exit_use_ctrl() -> bool {
+    true
+}
+
+fn default_exit_use_shift() -> bool {
+    true
+}
+
+fn default_exit_use_alt() -> bool {
+    false
+}
+
+fn default_exit_key() -> String {
+    "Q".to_string()
+}
+
+pub(crate) fn get_exe_dir() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+        .unwrap_or_else(|| std::env::current_dir().unwrap())
+}
+
+/// Loads `prompt.txt`, trying the working directory before the executable's
+/// own directory, and falling back to a generic instruction if neither has
+/// one.
+pub(crate) fn read_prompt(exe_dir: &PathBuf) -> Result<String> {
+    // Try working directory first
+    let working_dir = std::env::current_dir()?;
+    let prompt_path_working = working_dir.join("prompt.txt");
+
+    if let Ok(content) = fs::read_to_string(&prompt_path_working) {
+        println!("✅ Prompt loaded from: {}", prompt_path_working.display());
+        return Ok(content);
+    }
+
+    // Fall back to executable directory
+    let prompt_path_exe = exe_dir.join("prompt.txt");
+    match fs::read_to_string(&prompt_path_exe) {
+        Ok(content) => {
+            println!("✅ Prompt loaded from: {}", prompt_path_exe.display());
+            Ok(content)
+        }
+        Err(e) => {
+            eprintln!(
+                "❌ Failed to read prompt.txt from both working directory and executable directory: {}",
+                e
+            );
+            Ok("Please process the following text:".to_string())
+        }
+    }
+}
+
+pub(crate) fn read_config(exe_dir: &PathBuf) -> Result<Config> {
+    // Try working directory first
+    let working_dir = std::env::current_dir()?;
+    let config_path_working = working_dir.join("config.yaml");
+
+    if let Ok(content) = fs::read_to_string(&config_path_working) {
+        match serde_yaml::from_str::<Config>(&content) {
+            Ok(config) => {
+                println!("✅ Config loaded from: {}", config_path_working.display());
+                return Ok(config);
+            }
+            Err(e) => {
+                eprintln!("⚠️  Invalid config.yaml format in working directory: {}", e);
+            }
+        }
+    }
+
+    // Fall back to executable directory
+    let config_path_exe = exe_dir.join("config.yaml");
+    let content = fs::read_to_string(&config_path_exe).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to read config.yaml from both working directory and executable directory: {}",
+            e
+        )
+    })?;
+
+    let config: Config = serde_yaml::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Invalid config.yaml format: {}", e))?;
+
+    println!("✅ Config loaded from: {}", config_path_exe.display());
+    Ok(config)
+}
+
+/// Resolves `config.actions` into fully-loaded `Action`s. When
+/// `config.yaml` has no `actions` section at all, synthesizes a single
+/// "default" action from `prompt.txt` and the old default chord
+/// (Ctrl+Shift+P), so existing single-prompt configs keep working
+/// unchanged.
+pub(crate) fn resolve_actions(config: &Config, exe_dir: &PathBuf) -> Result<Vec<Action>> {
+    if config.actions.is_empty() {
+        let prompt = read_prompt(exe_dir)?;
+        return Ok(vec![Action {
+            name: "default".to_string(),
+            prompt,
+            use_ctrl: default_use_ctrl(),
+            use_shift: default_use_shift(),
+            use_alt: default_use_alt(),
+            key: default_trigger_key(),
+        }]);
+    }
+
+    config
+        .actions
+        .iter()
+        .map(|action| {
+            let prompt = resolve_action_prompt(action);
+            Ok(Action {
+                name: action.name.clone(),
+                prompt,
+                use_ctrl: action.use_ctrl,
+                use_shift: action.use_shift,
+                use_alt: action.use_alt,
+                key: action.key.clone(),
+            })
+        })
+        .collect()
+}
+
+fn resolve_action_prompt(action: &ActionConfig) -> String {
+    if let Some(prompt) = &action.prompt {
+        return prompt.clone();
+    }
+
+    let path = action
+        .prompt_file
+        .clone()
+        .unwrap_or_else(|| "prompt.txt".to_string());
+
+    match fs::read_to_string(&path) {
+        Ok(content) => {
+            println!(
+                "✅ Prompt for action \"{}\" loaded from: {}",
+                action.name, path
+            );
+            content
+        }
+        Err(e) => {
+            eprintln!(
+                "❌ Failed to read prompt file \"{}\" for action \"{}\": {}",
+                path, action.name, e
+            );
+            "Please process the following text:".to_string()
+        }
+    }
+}
+
+pub(crate) fn build_action_shortcut_text(action: &Action) -> String {
+    let mut parts = Vec::new();
+    if action.use_ctrl {
+        parts.push("Ctrl".to_string());
+    }
+    if action.use_shift {
+        parts.push("Shift".to_string());
+    }
+    if action.use_alt {
+        parts.push("Alt".to_string());
+    }
+    parts.push(action.key.clone());
+    parts.join("+")
+}
+
+pub(crate) fn build_exit_shortcut_text(config: &Config) -> String {
+    let mut parts = Vec::new();
+    if config.exit_use_ctrl {
+        parts.push("Ctrl".to_string());
+    }
+    if config.exit_use_shift {
+        parts.push("Shift".to_string());
+    }
+    if config.exit_use_alt {
+        parts.push("Alt".to_string());
+    }
+    parts.push(config.exit_key.clone());
+    parts.join("+")
+}
+
+/// One "name: chord" line per action, used for both the tray tooltip and
+/// the startup log.
+pub(crate) fn build_actions_summary(actions: &[Action]) -> String {
+    actions
+        .iter()
+        .map(|action| format!("{}: {}", action.name, build_action_shortcut_text(action)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Every file whose content can affect `config`/the resolved actions, for
+/// the file watcher to keep an eye on: `config.yaml` and `prompt.txt` in
+/// both of the directories `read_config`/`read_prompt` check, plus each
+/// action's `prompt_file`.
+pub(crate) fn watched_paths(config: &Config, exe_dir: &PathBuf) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    if let Ok(working_dir) = std::env::current_dir() {
+        paths.push(working_dir.join("config.yaml"));
+        paths.push(working_dir.join("prompt.txt"));
+    }
+    paths.push(exe_dir.join("config.yaml"));
+    paths.push(exe_dir.join("prompt.txt"));
+
+    for action in &config.actions {
+        if let Some(prompt_file) = &action.prompt_file {
+            paths.push(PathBuf::from(prompt_file));
+        }
+    }
+
+    paths
+}
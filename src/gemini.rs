@@ -0,0 +1,198 @@
+//! Gemini API client.
+//!
+//! `call_gemini_api_streaming` talks to the `streamGenerateContent` SSE
+//! endpoint so callers can act on each text delta as it arrives instead of
+//! waiting for the whole response; `call_gemini_api` is the older
+//! single-shot `generateContent` call, kept around as the fallback for
+//! when the streaming request itself fails.
+
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+const MODEL: &str = "gemini-2.5-flash-lite-preview-06-17";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GeminiRequest {
+    contents: Vec<GeminiContent>,
+    generation_config: GenerationConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GeminiContent {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Part {
+    text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GenerationConfig {
+    temperature: f32,
+    top_p: f32,
+    top_k: i32,
+    max_output_tokens: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiResponse {
+    candidates: Vec<Candidate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Candidate {
+    content: GeminiContent,
+}
+
+fn build_request(prompt: &str, selected_text: &str) -> GeminiRequest {
+    let full_prompt = format!("{}\n\nSelected text: {}", prompt, selected_text);
+
+    GeminiRequest {
+        contents: vec![GeminiContent {
+            parts: vec![Part { text: full_prompt }],
+        }],
+        generation_config: GenerationConfig {
+            temperature: 0.7,
+            top_p: 0.8,
+            top_k: 40,
+            max_output_tokens: 2048,
+        },
+    }
+}
+
+/// Single-shot, non-streaming call to `generateContent`. Used directly by
+/// nothing anymore, but kept as the fallback `call_gemini_api_streaming`
+/// reaches for when the SSE endpoint can't be used.
+pub(crate) async fn call_gemini_api(api_key: &str, prompt: &str, selected_text: &str) -> Result<String> {
+    let request = build_request(prompt, selected_text);
+
+    println!("🤖 Sending request to Gemini API...");
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent",
+            MODEL
+        ))
+        .query(&[("key", api_key)])
+        .json(&request)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await?;
+        return Err(anyhow::anyhow!("Gemini API error: {}", error_text));
+    }
+
+    let gemini_response: GeminiResponse = response.json().await?;
+
+    if let Some(candidate) = gemini_response.candidates.first() {
+        if let Some(part) = candidate.content.parts.first() {
+            println!("✅ Received response from Gemini");
+            return Ok(part.text.clone());
+        }
+    }
+
+    Ok("No response from Gemini".to_string())
+}
+
+/// Streams the response from `streamGenerateContent`, invoking `on_delta`
+/// with each text chunk as it arrives so the caller can, e.g., type it out
+/// live. Falls back to `call_gemini_api` (and a single `on_delta` call with
+/// the whole response) if the streaming request fails before any delta was
+/// typed. Once a delta has reached `on_delta`, a later failure (e.g. a
+/// dropped connection mid-stream) is reported as an error instead of
+/// falling back, since re-typing the full response on top of what's
+/// already been typed would duplicate text in the user's document.
+/// `data:` payloads that don't parse as a response chunk are skipped.
+pub(crate) async fn call_gemini_api_streaming(
+    api_key: &str,
+    prompt: &str,
+    selected_text: &str,
+    mut on_delta: impl FnMut(&str),
+) -> Result<String> {
+    let mut typed_any = false;
+    match stream_response(api_key, prompt, selected_text, &mut on_delta, &mut typed_any).await {
+        Ok(full_response) => Ok(full_response),
+        Err(e) if typed_any => Err(e).context("streaming request failed after partial output was already typed"),
+        Err(e) => {
+            eprintln!(
+                "⚠️  Streaming request failed ({}), falling back to non-streaming call",
+                e
+            );
+            let full_response = call_gemini_api(api_key, prompt, selected_text).await?;
+            on_delta(&full_response);
+            Ok(full_response)
+        }
+    }
+}
+
+async fn stream_response(
+    api_key: &str,
+    prompt: &str,
+    selected_text: &str,
+    on_delta: &mut impl FnMut(&str),
+    typed_any: &mut bool,
+) -> Result<String> {
+    let request = build_request(prompt, selected_text);
+
+    println!("🤖 Sending streaming request to Gemini API...");
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent",
+            MODEL
+        ))
+        .query(&[("key", api_key), ("alt", "sse")])
+        .json(&request)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await?;
+        return Err(anyhow::anyhow!("Gemini API error: {}", error_text));
+    }
+
+    let mut stream = response.bytes_stream();
+    // Bytes that haven't formed a complete line yet. We only ever parse
+    // complete lines out of here, so a chunk boundary landing mid
+    // multi-byte UTF-8 character never gets split.
+    let mut line_buffer: Vec<u8> = Vec::new();
+    let mut full_response = String::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("error reading Gemini response stream")?;
+        line_buffer.extend_from_slice(&chunk);
+
+        while let Some(newline_pos) = line_buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = line_buffer.drain(..=newline_pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim();
+
+            let Some(payload) = line.strip_prefix("data:") else {
+                continue;
+            };
+            let payload = payload.trim();
+            if payload.is_empty() {
+                continue;
+            }
+
+            let Ok(chunk) = serde_json::from_str::<GeminiResponse>(payload) else {
+                continue;
+            };
+            if let Some(candidate) = chunk.candidates.first() {
+                if let Some(part) = candidate.content.parts.first() {
+                    on_delta(&part.text);
+                    *typed_any = true;
+                    full_response.push_str(&part.text);
+                }
+            }
+        }
+    }
+
+    println!("✅ Finished streaming response from Gemini");
+    Ok(full_response)
+}
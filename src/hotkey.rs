@@ -0,0 +1,237 @@
+//! OS-level hotkey registration, built on `global-hotkey`.
+//!
+//! Replaces the old approach of listening to every raw key event and
+//! reconstructing modifier state by hand: each configured action's chord
+//! is registered once with the OS, which then tells us only when it fires
+//! and which one it was. Registrations live behind a lock so `reload` can
+//! swap them out for a freshly-edited config without restarting the app.
+
+use anyhow::{Context, Result};
+use global_hotkey::{
+    hotkey::{HotKey, Modifiers},
+    GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState,
+};
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc, RwLock};
+
+use crate::clipboard::SharedClipboardProvider;
+use crate::config::{Action, Config};
+use crate::keymap::parse_key_code;
+
+/// What the hotkey thread hands off to `process_text`: which action fired
+/// and the text to send to Gemini.
+pub struct SelectionEvent {
+    pub action_name: String,
+    pub selected_text: String,
+}
+
+/// Everything that can come down the channel shared by the hotkey service
+/// and the tray menu: either run an action, or reload config/prompts.
+pub enum AppEvent {
+    Process(SelectionEvent),
+    Reload,
+}
+
+/// The chords currently registered with the OS, kept behind a lock so
+/// `reload` can swap them out from the event-loop thread while the
+/// listener thread reads them.
+struct Registrations {
+    hotkeys: Vec<HotKey>,
+    action_ids: HashMap<u32, String>,
+    exit_id: u32,
+}
+
+/// Owns the OS hotkey registrations for every configured action plus the
+/// exit chord.
+pub struct HotkeyService {
+    manager: GlobalHotKeyManager,
+    registrations: Arc<RwLock<Registrations>>,
+    clipboard: SharedClipboardProvider,
+}
+
+impl HotkeyService {
+    pub fn new(
+        config: &Config,
+        actions: &[Action],
+        clipboard: SharedClipboardProvider,
+    ) -> Result<Self> {
+        let manager =
+            GlobalHotKeyManager::new().context("failed to initialize global hotkey manager")?;
+        let registrations = register(&manager, config, actions)?;
+
+        Ok(Self {
+            manager,
+            registrations: Arc::new(RwLock::new(registrations)),
+            clipboard,
+        })
+    }
+
+    /// Spawns the background thread that waits for `GlobalHotKeyEvent`s and
+    /// forwards the matching action's selected text to `sender`.
+    pub fn listen(&self, sender: mpsc::Sender<AppEvent>) {
+        let registrations = Arc::clone(&self.registrations);
+        let clipboard = Arc::clone(&self.clipboard);
+
+        std::thread::spawn(move || {
+            let receiver = GlobalHotKeyEvent::receiver();
+            for event in receiver.iter() {
+                if event.state != HotKeyState::Pressed {
+                    continue;
+                }
+
+                let regs = registrations.read().unwrap();
+                if event.id == regs.exit_id {
+                    println!("👋 Exit shortcut pressed. Shutting down...");
+                    std::process::exit(0);
+                } else if let Some(action_name) = regs.action_ids.get(&event.id) {
+                    let action_name = action_name.clone();
+                    drop(regs);
+                    println!("🔥 \"{}\" hotkey pressed! Processing selected text...", action_name);
+                    read_selection_and_send(action_name, &sender, &clipboard);
+                }
+            }
+        });
+    }
+
+    /// Unregisters the current chords and registers the ones from a
+    /// freshly reloaded `config`/`actions`, so edits take effect without
+    /// restarting. The old chords must come down first: `global-hotkey`
+    /// errors (`AlreadyRegistered`, `BadAccess` on X11) when asked to
+    /// register a chord that's still live, so if unchanged chords were
+    /// registered before the old ones were dropped, every reload that
+    /// keeps even one chord the same would fail outright. On failure the
+    /// old chords are re-registered so the previous registrations are left
+    /// in place untouched.
+    pub fn reload(&self, config: &Config, actions: &[Action]) -> Result<()> {
+        let mut current = self.registrations.write().unwrap();
+        self.manager
+            .unregister_all(&current.hotkeys)
+            .context("failed to unregister previous hotkeys")?;
+
+        match register(&self.manager, config, actions) {
+            Ok(new_registrations) => {
+                *current = new_registrations;
+                Ok(())
+            }
+            Err(e) => {
+                self.manager
+                    .register_all(&current.hotkeys)
+                    .context("failed to re-register previous hotkeys after a failed reload")?;
+                Err(e)
+            }
+        }
+    }
+}
+
+fn register(
+    manager: &GlobalHotKeyManager,
+    config: &Config,
+    actions: &[Action],
+) -> Result<Registrations> {
+    let mut hotkeys = Vec::new();
+    let mut action_ids = HashMap::new();
+
+    let result = (|| -> Result<u32> {
+        for action in actions {
+            let hotkey =
+                build_hotkey(action.use_ctrl, action.use_shift, action.use_alt, &action.key)
+                    .with_context(|| {
+                        format!("invalid key for action \"{}\": {}", action.name, action.key)
+                    })?;
+            manager.register(hotkey).with_context(|| {
+                format!("failed to register hotkey for action \"{}\"", action.name)
+            })?;
+            action_ids.insert(hotkey.id(), action.name.clone());
+            hotkeys.push(hotkey);
+        }
+
+        let exit = build_hotkey(
+            config.exit_use_ctrl,
+            config.exit_use_shift,
+            config.exit_use_alt,
+            &config.exit_key,
+        )
+        .with_context(|| format!("invalid exit_key in config: {}", config.exit_key))?;
+        let exit_id = exit.id();
+        manager
+            .register(exit)
+            .context("failed to register exit hotkey")?;
+        hotkeys.push(exit);
+        Ok(exit_id)
+    })();
+
+    match result {
+        Ok(exit_id) => Ok(Registrations {
+            hotkeys,
+            action_ids,
+            exit_id,
+        }),
+        Err(e) => {
+            // Undo whatever got registered before the failure, so a caller
+            // can put its previous chords back without clashing with them.
+            let _ = manager.unregister_all(&hotkeys);
+            Err(e)
+        }
+    }
+}
+
+/// Reads the currently selected text (preferring the primary selection,
+/// falling back to the clipboard) and sends it as a `Process` event for
+/// `action_name`. Shared by the hotkey thread and the tray menu's "run
+/// action" items, since both just want to trigger an action on whatever
+/// text is currently selected. Takes the already-detected `clipboard`
+/// provider rather than detecting one itself, since this runs on every
+/// hotkey press and detection shells out to `which`.
+pub fn read_selection_and_send(
+    action_name: String,
+    sender: &mpsc::Sender<AppEvent>,
+    clipboard: &SharedClipboardProvider,
+) {
+    let mut provider = clipboard.lock().unwrap();
+
+    // Prefer the primary selection (X11/Wayland "select to copy") so the
+    // user doesn't need to Ctrl+C first; fall back to the clipboard on
+    // platforms without one (or an empty selection).
+    let selected_text = match provider.get_primary_selection() {
+        Ok(text) if !text.trim().is_empty() => text,
+        _ => match provider.get_clipboard() {
+            Ok(text) => text,
+            Err(_) => {
+                println!("❌ Failed to read clipboard");
+                return;
+            }
+        },
+    };
+
+    if selected_text.trim().is_empty() {
+        println!("⚠️  No text selected or clipboard is empty");
+        return;
+    }
+
+    println!("📝 Processing text: {}", selected_text);
+    let event = AppEvent::Process(SelectionEvent {
+        action_name,
+        selected_text,
+    });
+    if let Err(e) = sender.send(event) {
+        eprintln!("❌ Failed to send text to main thread: {}", e);
+    }
+}
+
+fn build_hotkey(use_ctrl: bool, use_shift: bool, use_alt: bool, key_str: &str) -> Result<HotKey> {
+    let code = parse_key_code(key_str)
+        .with_context(|| format!("unrecognized key name: {}", key_str))?;
+
+    let mut modifiers = Modifiers::empty();
+    if use_ctrl {
+        modifiers |= Modifiers::CONTROL;
+    }
+    if use_shift {
+        modifiers |= Modifiers::SHIFT;
+    }
+    if use_alt {
+        modifiers |= Modifiers::ALT;
+    }
+
+    Ok(HotKey::new(Some(modifiers), code))
+}
@@ -0,0 +1,140 @@
+//! Optional egui window that records every request/response pair sent to
+//! Gemini, so a user can review what was actually sent and recover a
+//! response that got typed into the wrong window.
+//!
+//! `winit` (which `eframe` is built on) requires its event loop to run on
+//! the OS main thread on macOS and Windows, so unlike the app's other
+//! background subsystems, the inspector can't just spawn its own thread:
+//! `new` only builds its state and channel, and `run` -- which the caller
+//! must invoke from the OS main thread -- blocks for the life of the window.
+
+use anyhow::Result;
+use eframe::egui;
+use std::sync::mpsc;
+
+use crate::clipboard::SharedClipboardProvider;
+
+/// One row of the inspector: what was sent to Gemini and what came back.
+#[derive(Debug, Clone)]
+pub struct InteractionRecord {
+    pub timestamp_secs: u64,
+    pub action_name: String,
+    pub prompt: String,
+    pub selected_text: String,
+    pub response: String,
+    pub latency_ms: u128,
+    pub error: Option<String>,
+}
+
+/// The inspector's state plus the sending half of the channel `process_text`
+/// pushes records through. Build with `new`, hand clones of `sender()` to
+/// whatever should feed it records, then call `run` from the OS main thread
+/// to actually open the window.
+pub struct Inspector {
+    app: InspectorApp,
+    sender: mpsc::Sender<InteractionRecord>,
+}
+
+impl Inspector {
+    pub fn new(clipboard: SharedClipboardProvider) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            app: InspectorApp::new(receiver, clipboard),
+            sender,
+        }
+    }
+
+    pub fn sender(&self) -> mpsc::Sender<InteractionRecord> {
+        self.sender.clone()
+    }
+
+    /// Runs the inspector window's event loop. Must be called from the OS
+    /// main thread; blocks until the window is closed.
+    pub fn run(self) -> Result<()> {
+        let options = eframe::NativeOptions::default();
+        eframe::run_native(
+            "Gemini Text Processor - Inspector",
+            options,
+            Box::new(|_cc| Box::new(self.app)),
+        )
+        .map_err(|e| anyhow::anyhow!("inspector window exited with error: {}", e))
+    }
+}
+
+struct InspectorApp {
+    receiver: mpsc::Receiver<InteractionRecord>,
+    records: Vec<InteractionRecord>,
+    filter: String,
+    clipboard: SharedClipboardProvider,
+}
+
+impl InspectorApp {
+    fn new(receiver: mpsc::Receiver<InteractionRecord>, clipboard: SharedClipboardProvider) -> Self {
+        Self {
+            receiver,
+            records: Vec::new(),
+            filter: String::new(),
+            clipboard,
+        }
+    }
+
+    fn matches_filter(&self, record: &InteractionRecord) -> bool {
+        if self.filter.is_empty() {
+            return true;
+        }
+        record.action_name.contains(&self.filter)
+            || record.prompt.contains(&self.filter)
+            || record.selected_text.contains(&self.filter)
+            || record.response.contains(&self.filter)
+    }
+}
+
+impl eframe::App for InspectorApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        while let Ok(record) = self.receiver.try_recv() {
+            self.records.push(record);
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Gemini request/response history");
+            ui.horizontal(|ui| {
+                ui.label("Filter:");
+                ui.text_edit_singleline(&mut self.filter);
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for record in self.records.iter().rev() {
+                    if !self.matches_filter(record) {
+                        continue;
+                    }
+
+                    ui.group(|ui| {
+                        let status = match &record.error {
+                            Some(error) => format!("error: {error}"),
+                            None => "ok".to_string(),
+                        };
+                        ui.label(format!(
+                            "#{} · {} · {}ms · {}",
+                            record.timestamp_secs, record.action_name, record.latency_ms, status
+                        ));
+                        ui.label(format!("Prompt: {}", record.prompt));
+                        ui.label(format!("Selected text: {}", record.selected_text));
+                        ui.label(format!("Response: {}", record.response));
+
+                        if ui.button("Copy response to clipboard").clicked() {
+                            let mut provider = self.clipboard.lock().unwrap();
+                            if let Err(e) = provider.set_clipboard(&record.response) {
+                                eprintln!("❌ Failed to copy response to clipboard: {}", e);
+                            }
+                        }
+                    });
+                }
+            });
+        });
+
+        // The channel is polled every frame rather than waking the UI
+        // thread, so keep repainting even with no user input.
+        ctx.request_repaint_after(std::time::Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,72 @@
+//! Maps human-readable key names from `config.yaml` (e.g. `"F5"`, `"Left"`,
+//! `"P"`) onto the physical `Code`s that `global-hotkey` registers with the OS.
+//!
+//! `Code` already has a `FromStr` impl covering every key it knows about
+//! (the DOM `KeyboardEvent.code` names: `"KeyA"`, `"Digit1"`, `"ArrowLeft"`,
+//! `"F1"`, `"Numpad5"`, ...), so the only thing missing is the handful of
+//! short names a config author would actually type (`"A"`, `"1"`, `"Left"`).
+//! `parse_key_code` resolves those aliases and falls through to `Code`'s own
+//! parser for everything else, so the full keyboard is reachable without a
+//! hand-maintained table per key.
+
+use global_hotkey::hotkey::Code;
+
+/// Parses a config key name into the `Code` `global-hotkey` expects.
+pub fn parse_key_code(key_str: &str) -> Option<Code> {
+    alias_code(key_str).or_else(|| key_str.parse().ok())
+}
+
+/// The short names worth special-casing because typing the DOM code name in
+/// full (`"KeyP"`, `"Digit1"`, `"ArrowLeft"`) would be unergonomic.
+fn alias_code(key_str: &str) -> Option<Code> {
+    use Code::*;
+    Some(match key_str {
+        "A" => KeyA,
+        "B" => KeyB,
+        "C" => KeyC,
+        "D" => KeyD,
+        "E" => KeyE,
+        "F" => KeyF,
+        "G" => KeyG,
+        "H" => KeyH,
+        "I" => KeyI,
+        "J" => KeyJ,
+        "K" => KeyK,
+        "L" => KeyL,
+        "M" => KeyM,
+        "N" => KeyN,
+        "O" => KeyO,
+        "P" => KeyP,
+        "Q" => KeyQ,
+        "R" => KeyR,
+        "S" => KeyS,
+        "T" => KeyT,
+        "U" => KeyU,
+        "V" => KeyV,
+        "W" => KeyW,
+        "X" => KeyX,
+        "Y" => KeyY,
+        "Z" => KeyZ,
+        "0" => Digit0,
+        "1" => Digit1,
+        "2" => Digit2,
+        "3" => Digit3,
+        "4" => Digit4,
+        "5" => Digit5,
+        "6" => Digit6,
+        "7" => Digit7,
+        "8" => Digit8,
+        "9" => Digit9,
+        "Left" => ArrowLeft,
+        "Right" => ArrowRight,
+        "Up" => ArrowUp,
+        "Down" => ArrowDown,
+        "Esc" => Escape,
+        "Return" => Enter,
+        "Del" => Delete,
+        "Ins" => Insert,
+        "PgUp" => PageUp,
+        "PgDn" => PageDown,
+        _ => return None,
+    })
+}
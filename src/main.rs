@@ -1,473 +1,222 @@
-use anyhow::Result;
-use arboard::Clipboard;
-use enigo;
-use enigo::KeyboardControllable;
-use rdev::{EventType, Key, listen};
-use reqwest;
-use serde::{Deserialize, Serialize};
-use std::fs;
+use anyhow::{Context, Result};
+use enigo::{Enigo, Keyboard, Settings};
+use notify_rust::Notification;
 use std::path::PathBuf;
 use std::sync::mpsc;
-use std::sync::{Arc, Mutex};
-use tokio;
-use tray_icon::TrayIconBuilder;
 
-#[derive(Debug, Deserialize, Clone)]
-struct Config {
-    gemini_api_key: String,
-    #[serde(default = "default_use_ctrl")]
-    use_ctrl: bool,
-    #[serde(default = "default_use_shift")]
-    use_shift: bool,
-    #[serde(default = "default_use_alt")]
-    use_alt: bool,
-    #[serde(default = "default_trigger_key")]
-    trigger_key: String,
-    #[serde(default = "default_exit_use_ctrl")]
-    exit_use_ctrl: bool,
-    #[serde(default = "default_exit_use_shift")]
-    exit_use_shift: bool,
-    #[serde(default = "default_exit_use_alt")]
-    exit_use_alt: bool,
-    #[serde(default = "default_exit_key")]
-    exit_key: String,
-}
-
-fn default_use_ctrl() -> bool {
-    true
-}
-
-fn default_use_shift() -> bool {
-    true
-}
-
-fn default_use_alt() -> bool {
-    false
-}
-
-fn default_trigger_key() -> String {
-    "P".to_string()
-}
-
-fn default_exit_use_ctrl() -> bool {
-    true
-}
-
-fn default_exit_use_shift() -> bool {
-    true
-}
-
-fn default_exit_use_alt() -> bool {
-    false
-}
-
-fn default_exit_key() -> String {
-    "Q".to_string()
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct GeminiRequest {
-    contents: Vec<GeminiContent>,
-    generation_config: GenerationConfig,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct GeminiContent {
-    parts: Vec<Part>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct Part {
-    text: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct GenerationConfig {
-    temperature: f32,
-    top_p: f32,
-    top_k: i32,
-    max_output_tokens: i32,
-}
-
-#[derive(Debug, Deserialize)]
-struct GeminiResponse {
-    candidates: Vec<Candidate>,
-}
-
-#[derive(Debug, Deserialize)]
-struct Candidate {
-    content: GeminiContent,
-}
-
-struct KeyState {
-    ctrl: bool,
-    shift: bool,
-    alt: bool,
+mod clipboard;
+mod config;
+mod gemini;
+mod hotkey;
+mod inspector;
+mod keymap;
+mod tray;
+mod watch;
+
+use config::{
+    build_actions_summary, build_exit_shortcut_text, get_exe_dir, read_config, resolve_actions,
+    watched_paths, Action, Config,
+};
+use gemini::call_gemini_api_streaming;
+use hotkey::{AppEvent, HotkeyService, SelectionEvent};
+use inspector::{InteractionRecord, Inspector};
+use tray::Tray;
+
+fn notify(summary: &str, body: &str) {
+    if let Err(e) = Notification::new().summary(summary).body(body).show() {
+        eprintln!("⚠️  Failed to show notification: {}", e);
+    }
 }
 
-fn get_exe_dir() -> PathBuf {
-    std::env::current_exe()
-        .ok()
-        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
-        .unwrap_or_else(|| std::env::current_dir().unwrap())
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
-fn read_prompt(exe_dir: &PathBuf) -> Result<String> {
-    // Try working directory first
-    let working_dir = std::env::current_dir()?;
-    let prompt_path_working = working_dir.join("prompt.txt");
+async fn process_text(
+    config: Config,
+    actions: Vec<Action>,
+    event: SelectionEvent,
+    inspector_sender: Option<mpsc::Sender<InteractionRecord>>,
+) {
+    let Some(action) = actions.iter().find(|a| a.name == event.action_name) else {
+        eprintln!("❌ No action named \"{}\" configured", event.action_name);
+        return;
+    };
 
-    if let Ok(content) = fs::read_to_string(&prompt_path_working) {
-        println!("✅ Prompt loaded from: {}", prompt_path_working.display());
-        return Ok(content);
-    }
+    notify("Gemini Text Processor", &format!("Processing ({})…", action.name));
 
-    // Fall back to executable directory
-    let prompt_path_exe = exe_dir.join("prompt.txt");
-    match fs::read_to_string(&prompt_path_exe) {
-        Ok(content) => {
-            println!("✅ Prompt loaded from: {}", prompt_path_exe.display());
-            Ok(content)
-        }
+    let mut enigo = match Enigo::new(&Settings::default()).context("failed to connect to the input backend") {
+        Ok(enigo) => enigo,
         Err(e) => {
-            eprintln!(
-                "❌ Failed to read prompt.txt from both working directory and executable directory: {}",
-                e
-            );
-            Ok("Please process the following text:".to_string())
+            eprintln!("❌ Error typing Gemini response: {}", e);
+            notify("Gemini Text Processor - Error", &e.to_string());
+            return;
         }
-    }
-}
-
-fn read_config(exe_dir: &PathBuf) -> Result<Config> {
-    // Try working directory first
-    let working_dir = std::env::current_dir()?;
-    let config_path_working = working_dir.join("config.yaml");
-
-    if let Ok(content) = fs::read_to_string(&config_path_working) {
-        match serde_yaml::from_str::<Config>(&content) {
-            Ok(config) => {
-                println!("✅ Config loaded from: {}", config_path_working.display());
-                return Ok(config);
-            }
-            Err(e) => {
-                eprintln!("⚠️  Invalid config.yaml format in working directory: {}", e);
-            }
+    };
+    let type_delta = |delta: &str| {
+        if let Err(e) = enigo.text(delta) {
+            eprintln!("❌ Failed to type response delta: {}", e);
         }
-    }
-
-    // Fall back to executable directory
-    let config_path_exe = exe_dir.join("config.yaml");
-    let content = fs::read_to_string(&config_path_exe).map_err(|e| {
-        anyhow::anyhow!(
-            "Failed to read config.yaml from both working directory and executable directory: {}",
-            e
-        )
-    })?;
-
-    let config: Config = serde_yaml::from_str(&content)
-        .map_err(|e| anyhow::anyhow!("Invalid config.yaml format: {}", e))?;
-
-    println!("✅ Config loaded from: {}", config_path_exe.display());
-    Ok(config)
-}
-
-async fn call_gemini_api(api_key: &str, prompt: &str, selected_text: &str) -> Result<String> {
-    let full_prompt = format!("{}\n\nSelected text: {}", prompt, selected_text);
-
-    let request = GeminiRequest {
-        contents: vec![GeminiContent {
-            parts: vec![Part { text: full_prompt }],
-        }],
-        generation_config: GenerationConfig {
-            temperature: 0.7,
-            top_p: 0.8,
-            top_k: 40,
-            max_output_tokens: 2048,
-        },
     };
 
-    println!("🤖 Sending request to Gemini API...");
-
-    let client = reqwest::Client::new();
-    let response = client
-        .post("https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-flash-lite-preview-06-17:generateContent")
-        .query(&[("key", api_key)])
-        .json(&request)
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        let error_text = response.text().await?;
-        return Err(anyhow::anyhow!("Gemini API error: {}", error_text));
-    }
-
-    let gemini_response: GeminiResponse = response.json().await?;
-
-    if let Some(candidate) = gemini_response.candidates.first() {
-        if let Some(part) = candidate.content.parts.first() {
-            println!("✅ Received response from Gemini");
-            return Ok(part.text.clone());
+    let started_at = std::time::Instant::now();
+    let result = call_gemini_api_streaming(
+        &config.gemini_api_key,
+        &action.prompt,
+        &event.selected_text,
+        type_delta,
+    )
+    .await;
+    let latency_ms = started_at.elapsed().as_millis();
+
+    let response = match &result {
+        Ok(response) => {
+            println!("📋 Gemini response: {}", response);
+            notify("Gemini Text Processor", "Response typed");
+            response.clone()
         }
-    }
-
-    Ok("No response from Gemini".to_string())
-}
-
-fn setup_tray(config: &Config) -> Result<tray_icon::TrayIcon> {
-    let shortcut_text = build_shortcut_text(config);
-    let exit_shortcut_text = build_exit_shortcut_text(config);
-    
-    // Create the tray icon without menu
-    let tray_icon = TrayIconBuilder::new()
-        .with_tooltip(format!("Press {} to process text\nPress {} to exit", shortcut_text, exit_shortcut_text))
-        .build()
-        .map_err(|e| anyhow::anyhow!("Failed to create tray icon: {}", e))?;
-    
-    Ok(tray_icon)
-}
-
-fn build_shortcut_text(config: &Config) -> String {
-    let mut parts = Vec::new();
-    if config.use_ctrl {
-        parts.push("Ctrl".to_string());
-    }
-    if config.use_shift {
-        parts.push("Shift".to_string());
-    }
-    if config.use_alt {
-        parts.push("Alt".to_string());
-    }
-    parts.push(config.trigger_key.clone());
-    parts.join("+")
-}
+        Err(e) => {
+            eprintln!("❌ Error calling Gemini API: {}", e);
+            notify("Gemini Text Processor - Error", &e.to_string());
+            String::new()
+        }
+    };
 
-fn build_exit_shortcut_text(config: &Config) -> String {
-    let mut parts = Vec::new();
-    if config.exit_use_ctrl {
-        parts.push("Ctrl".to_string());
-    }
-    if config.exit_use_shift {
-        parts.push("Shift".to_string());
+    if let Some(sender) = inspector_sender {
+        let record = InteractionRecord {
+            timestamp_secs: now_secs(),
+            action_name: action.name.clone(),
+            prompt: action.prompt.clone(),
+            selected_text: event.selected_text,
+            response,
+            latency_ms,
+            error: result.err().map(|e| e.to_string()),
+        };
+        let _ = sender.send(record);
     }
-    if config.exit_use_alt {
-        parts.push("Alt".to_string());
-    }
-    parts.push(config.exit_key.clone());
-    parts.join("+")
 }
 
-fn handle_hotkey(sender: mpsc::Sender<String>, config: Config) {
-    let key_state = Arc::new(Mutex::new(KeyState {
-        ctrl: false,
-        shift: false,
-        alt: false,
-    }));
-
-    std::thread::spawn(move || {
-        listen(move |event| {
-            let mut state = key_state.lock().unwrap();
+/// Handles `AppEvent`s from the hotkey/tray/watch threads until the channel
+/// disconnects: spawns `process_text` for each `Process` event and re-reads
+/// config/prompts on each `Reload`.
+async fn run_event_loop(
+    exe_dir: PathBuf,
+    mut config: Config,
+    mut actions: Vec<Action>,
+    receiver: mpsc::Receiver<AppEvent>,
+    hotkey_service: HotkeyService,
+    inspector_sender: Option<mpsc::Sender<InteractionRecord>>,
+) {
+    loop {
+        match receiver.recv_timeout(std::time::Duration::from_millis(100)) {
+            Ok(AppEvent::Process(event)) => {
+                let config = config.clone();
+                let actions = actions.clone();
+                let inspector_sender = inspector_sender.clone();
 
-            match event.event_type {
-                EventType::KeyPress(key) => {
-                    match key {
-                        Key::ControlLeft | Key::ControlRight => {
-                            state.ctrl = true;
-                        }
-                        Key::ShiftLeft | Key::ShiftRight => {
-                            state.shift = true;
-                        }
-                        Key::Alt | Key::AltGr => {
-                            state.alt = true;
+                tokio::spawn(async move {
+                    process_text(config, actions, event, inspector_sender).await;
+                });
+            }
+            Ok(AppEvent::Reload) => match read_config(&exe_dir).and_then(|c| {
+                let resolved = resolve_actions(&c, &exe_dir)?;
+                Ok((c, resolved))
+            }) {
+                Ok((new_config, new_actions)) => {
+                    match hotkey_service.reload(&new_config, &new_actions) {
+                        Ok(()) => {
+                            config = new_config;
+                            actions = new_actions;
+                            println!("🔄 Config and prompts reloaded");
+                            notify("Gemini Text Processor", "Config and prompts reloaded");
                         }
-                        _ => {
-                            // Check if this is the trigger key
-                            if let Some(trigger_key) = parse_trigger_key(&config.trigger_key) {
-                                if key == trigger_key {
-                                    let ctrl_pressed = !config.use_ctrl || state.ctrl;
-                                    let shift_pressed = !config.use_shift || state.shift;
-                                    let alt_pressed = !config.use_alt || state.alt;
-                                    
-                                    if ctrl_pressed && shift_pressed && alt_pressed {
-                                        println!("🔥 Hotkey pressed! Processing selected text...");
-                                        
-                                        // Get selected text from clipboard
-                                        if let Ok(mut clipboard) = Clipboard::new() {
-                                            if let Ok(selected_text) = clipboard.get_text() {
-                                                if !selected_text.trim().is_empty() {
-                                                    println!("📝 Processing text: {}", selected_text);
-                                                    if let Err(e) = sender.send(selected_text) {
-                                                        eprintln!(
-                                                            "❌ Failed to send text to main thread: {}",
-                                                            e
-                                                        );
-                                                    }
-                                                } else {
-                                                    println!("⚠️  No text selected or clipboard is empty");
-                                                }
-                                            } else {
-                                                println!("❌ Failed to read clipboard");
-                                            }
-                                        } else {
-                                            println!("❌ Failed to access clipboard");
-                                        }
-                                    }
-                                }
-                            }
-                            
-                            // Check for exit shortcut
-                            if let Some(exit_key) = parse_trigger_key(&config.exit_key) {
-                                if key == exit_key {
-                                    let ctrl_pressed = !config.exit_use_ctrl || state.ctrl;
-                                    let shift_pressed = !config.exit_use_shift || state.shift;
-                                    let alt_pressed = !config.exit_use_alt || state.alt;
-                                    
-                                    if ctrl_pressed && shift_pressed && alt_pressed {
-                                        println!("👋 Exit shortcut pressed. Shutting down...");
-                                        std::process::exit(0);
-                                    }
-                                }
-                            }
+                        Err(e) => {
+                            eprintln!(
+                                "❌ Failed to register reloaded hotkeys, keeping previous config: {}",
+                                e
+                            );
+                            notify(
+                                "Gemini Text Processor - Error",
+                                &format!("Reload failed: {e}"),
+                            );
                         }
                     }
                 }
-                EventType::KeyRelease(key) => match key {
-                    Key::ControlLeft | Key::ControlRight => {
-                        state.ctrl = false;
-                    }
-                    Key::ShiftLeft | Key::ShiftRight => {
-                        state.shift = false;
-                    }
-                    Key::Alt | Key::AltGr => {
-                        state.alt = false;
-                    }
-                    _ => {}
-                },
-                _ => {}
-            }
-        })
-        .unwrap();
-    });
-}
-
-fn parse_trigger_key(key_str: &str) -> Option<Key> {
-    match key_str.to_uppercase().as_str() {
-        "A" => Some(Key::KeyA),
-        "B" => Some(Key::KeyB),
-        "C" => Some(Key::KeyC),
-        "D" => Some(Key::KeyD),
-        "E" => Some(Key::KeyE),
-        "F" => Some(Key::KeyF),
-        "G" => Some(Key::KeyG),
-        "H" => Some(Key::KeyH),
-        "I" => Some(Key::KeyI),
-        "J" => Some(Key::KeyJ),
-        "K" => Some(Key::KeyK),
-        "L" => Some(Key::KeyL),
-        "M" => Some(Key::KeyM),
-        "N" => Some(Key::KeyN),
-        "O" => Some(Key::KeyO),
-        "P" => Some(Key::KeyP),
-        "Q" => Some(Key::KeyQ),
-        "R" => Some(Key::KeyR),
-        "S" => Some(Key::KeyS),
-        "T" => Some(Key::KeyT),
-        "U" => Some(Key::KeyU),
-        "V" => Some(Key::KeyV),
-        "W" => Some(Key::KeyW),
-        "X" => Some(Key::KeyX),
-        "Y" => Some(Key::KeyY),
-        "Z" => Some(Key::KeyZ),
-        "0" => Some(Key::Num0),
-        "1" => Some(Key::Num1),
-        "2" => Some(Key::Num2),
-        "3" => Some(Key::Num3),
-        "4" => Some(Key::Num4),
-        "5" => Some(Key::Num5),
-        "6" => Some(Key::Num6),
-        "7" => Some(Key::Num7),
-        "8" => Some(Key::Num8),
-        "9" => Some(Key::Num9),
-        _ => None,
-    }
-}
-
-async fn process_text(prompt: String, config: Config, selected_text: String) {
-    match call_gemini_api(&config.gemini_api_key, &prompt, &selected_text).await {
-        Ok(response) => {
-            println!("📋 Gemini response: {}", response);
-
-            // Copy response to clipboard
-            if let Ok(mut clipboard) = Clipboard::new() {
-                if let Err(e) = clipboard.set_text(response.clone()) {
-                    eprintln!("❌ Failed to set clipboard: {}", e);
-                    return;
+                Err(e) => {
+                    eprintln!("❌ Failed to reload config, keeping previous values: {}", e);
+                    notify("Gemini Text Processor - Error", &format!("Reload failed: {e}"));
                 }
-                println!("✅ Response copied to clipboard");
+            },
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                // Continue loop
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                eprintln!("❌ Hotkey thread disconnected");
+                break;
             }
-
-            // Simulate Ctrl+V to paste the response
-            std::thread::sleep(std::time::Duration::from_millis(100));
-            let mut enigo = enigo::Enigo::new();
-            enigo.key_down(enigo::Key::Control);
-            enigo.key_click(enigo::Key::Layout('v'));
-            enigo.key_up(enigo::Key::Control);
-            println!("✅ Response pasted");
-        }
-        Err(e) => {
-            eprintln!("❌ Error calling Gemini API: {}", e);
         }
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+/// Not `#[tokio::main]`: the inspector window's `eframe`/`winit` event loop
+/// must run on the OS main thread on macOS and Windows, so this thread
+/// builds everything, then either blocks in `Inspector::run` (if enabled)
+/// or just waits for `run_event_loop`, which always runs on its own thread
+/// with its own Tokio runtime so it doesn't depend on which thread hosts
+/// the inspector.
+fn main() -> Result<()> {
     println!("🚀 Starting Gemini Text Processor...");
 
     let exe_dir = get_exe_dir();
-    let prompt = read_prompt(&exe_dir)?;
     let config = read_config(&exe_dir)?;
+    let actions = resolve_actions(&config, &exe_dir)?;
 
-    println!("📄 Base prompt: {}", prompt);
+    let clipboard = clipboard::shared_provider();
 
-    let _tray_icon = setup_tray(&config)?;
+    let tray = Tray::new(&config, &actions, clipboard.clone())?;
     println!("✅ Tray icon created successfully");
 
-    let prompt_clone = prompt.clone();
+    let inspector = if config.enable_inspector {
+        println!("🔎 Inspector window enabled");
+        Some(Inspector::new(clipboard.clone()))
+    } else {
+        None
+    };
+    let inspector_sender = inspector.as_ref().map(Inspector::sender);
 
-    // Create channel for communication between hotkey thread and main async runtime
+    // Create channel shared by the hotkey service and the tray menu
     let (sender, receiver) = mpsc::channel();
 
-    handle_hotkey(sender, config.clone());
+    let hotkey_service = HotkeyService::new(&config, &actions, clipboard)?;
+    hotkey_service.listen(sender.clone());
+    watch::watch(watched_paths(&config, &exe_dir), sender.clone())?;
+    tray.listen(sender);
 
     println!("✅ Application started successfully!");
-    let shortcut_text = build_shortcut_text(&config);
+    println!("{}", build_actions_summary(&actions));
     let exit_shortcut_text = build_exit_shortcut_text(&config);
-    println!("📌 Press {} to process selected text", shortcut_text);
     println!("📌 Press {} to exit the application", exit_shortcut_text);
     println!("🖥️  Check the system tray for the application icon");
 
-    // Main loop to handle incoming text from hotkey
-    loop {
-        // Check for hotkey events
-        match receiver.recv_timeout(std::time::Duration::from_millis(100)) {
-            Ok(selected_text) => {
-                let prompt = prompt_clone.clone();
-                let config = config.clone();
+    let event_loop = std::thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().expect("failed to start Tokio runtime");
+        runtime.block_on(run_event_loop(
+            exe_dir,
+            config,
+            actions,
+            receiver,
+            hotkey_service,
+            inspector_sender,
+        ));
+    });
 
-                tokio::spawn(async move {
-                    process_text(prompt, config, selected_text).await;
-                });
-            }
-            Err(mpsc::RecvTimeoutError::Timeout) => {
-                // Continue loop
-            }
-            Err(mpsc::RecvTimeoutError::Disconnected) => {
-                eprintln!("❌ Hotkey thread disconnected");
-                break;
-            }
-        }
+    if let Some(inspector) = inspector {
+        inspector.run()?;
     }
 
+    event_loop.join().expect("event loop thread panicked");
     Ok(())
 }
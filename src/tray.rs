@@ -0,0 +1,93 @@
+//! Interactive tray icon: a context menu for quitting, reloading config,
+//! and running a prompt action with the mouse instead of a chord —
+//! replacing the old tooltip-only icon that gave no way to observe or
+//! control the app without a terminal.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::mpsc;
+use tray_icon::menu::{Menu, MenuEvent, MenuId, MenuItem, PredefinedMenuItem, Submenu};
+use tray_icon::{TrayIcon, TrayIconBuilder};
+
+use crate::clipboard::SharedClipboardProvider;
+use crate::config::{build_actions_summary, build_exit_shortcut_text, Action, Config};
+use crate::hotkey::{self, AppEvent};
+
+/// Owns the tray icon and the ids needed to tell its menu items apart once
+/// `MenuEvent`s start arriving.
+pub struct Tray {
+    _tray_icon: TrayIcon,
+    quit_id: MenuId,
+    reload_id: MenuId,
+    action_items: HashMap<MenuId, String>,
+    clipboard: SharedClipboardProvider,
+}
+
+impl Tray {
+    pub fn new(
+        config: &Config,
+        actions: &[Action],
+        clipboard: SharedClipboardProvider,
+    ) -> Result<Self> {
+        let menu = Menu::new();
+
+        // Each action already has its own hotkey chord, so there's no
+        // separate "active" action to select — these items just run the
+        // action on whatever text is currently selected, same as its chord.
+        let action_submenu = Submenu::new("Run action now", true);
+        let mut action_items = HashMap::new();
+        for action in actions {
+            let item = MenuItem::new(&action.name, true, None);
+            action_submenu.append(&item)?;
+            action_items.insert(item.id().clone(), action.name.clone());
+        }
+        menu.append(&action_submenu)?;
+        menu.append(&PredefinedMenuItem::separator())?;
+
+        let reload_item = MenuItem::new("Reload config && prompt", true, None);
+        menu.append(&reload_item)?;
+
+        let quit_item = MenuItem::new("Quit", true, None);
+        menu.append(&quit_item)?;
+
+        let tooltip = format!(
+            "{}\nPress {} to exit",
+            build_actions_summary(actions),
+            build_exit_shortcut_text(config)
+        );
+
+        let tray_icon = TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip(tooltip)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to create tray icon: {}", e))?;
+
+        Ok(Self {
+            _tray_icon: tray_icon,
+            quit_id: quit_item.id().clone(),
+            reload_id: reload_item.id().clone(),
+            action_items,
+            clipboard,
+        })
+    }
+
+    /// Spawns the background thread that waits for `MenuEvent`s and
+    /// forwards them over the same channel the hotkey service uses.
+    pub fn listen(self, sender: mpsc::Sender<AppEvent>) {
+        std::thread::spawn(move || {
+            let receiver = MenuEvent::receiver();
+            for event in receiver.iter() {
+                if event.id == self.quit_id {
+                    println!("👋 Quit selected from tray menu. Shutting down...");
+                    std::process::exit(0);
+                } else if event.id == self.reload_id {
+                    if let Err(e) = sender.send(AppEvent::Reload) {
+                        eprintln!("❌ Failed to send reload request: {}", e);
+                    }
+                } else if let Some(action_name) = self.action_items.get(&event.id) {
+                    hotkey::read_selection_and_send(action_name.clone(), &sender, &self.clipboard);
+                }
+            }
+        });
+    }
+}
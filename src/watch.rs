@@ -0,0 +1,72 @@
+//! Watches `config.yaml`, `prompt.txt` and any per-action `prompt_file`s
+//! for changes and asks the main loop to reload once they settle, so
+//! tuning a prompt or a chord is a save-and-go loop instead of a
+//! kill-and-relaunch one.
+
+use anyhow::{Context, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::hotkey::AppEvent;
+
+/// How long to wait after the last filesystem event before reloading, so a
+/// burst of writes from an editor's save (temp file + rename, etc.) only
+/// triggers one reload.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Starts watching `paths` in the background, sending `AppEvent::Reload`
+/// through `sender` whenever one of them settles after a change.
+///
+/// Watches the containing directories rather than the files themselves,
+/// since editors commonly save by writing a temp file and renaming it over
+/// the original, which would otherwise drop the watch on the old inode.
+pub fn watch(paths: Vec<PathBuf>, sender: mpsc::Sender<AppEvent>) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    // Directory watches report every file in the directory; only changes
+    // to one of `paths` themselves should lead to a reload.
+    let watched_files = paths.clone();
+    let mut watcher: RecommendedWatcher =
+        notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                if event.paths.iter().any(|p| watched_files.contains(p)) {
+                    let _ = tx.send(event);
+                }
+            }
+        })
+        .context("failed to create config/prompt file watcher")?;
+
+    let mut watched_dirs = std::collections::HashSet::new();
+    for path in &paths {
+        if let Some(parent) = path.parent() {
+            if watched_dirs.insert(parent.to_path_buf()) {
+                watcher
+                    .watch(parent, RecursiveMode::NonRecursive)
+                    .with_context(|| format!("failed to watch {}", parent.display()))?;
+            }
+        }
+    }
+
+    std::thread::spawn(move || {
+        // Keep the watcher alive for as long as this thread runs; dropping
+        // it would stop delivering events.
+        let _watcher = watcher;
+
+        loop {
+            if rx.recv().is_err() {
+                break;
+            }
+            // Drain anything else that arrives within the debounce window
+            // so a single save only triggers one reload.
+            while rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+            println!("📄 Config or prompt file changed, reloading...");
+            if sender.send(AppEvent::Reload).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}